@@ -0,0 +1,62 @@
+//! Drives conda and PyPI dependency resolution for a project and persists the result to
+//! `pixi.lock`. See [`resolve`] for the actual solving and [`update_conda_lock`] for how a solve
+//! is wired up for a caller (e.g. `pixi install`).
+
+pub mod pypi;
+mod resolve;
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rattler_conda_types::RepoDataRecord;
+use rattler_lock::{PypiPackageData, PypiPackageEnvironmentData};
+
+pub use resolve::{lock_lock_file, resolve_conda, LockFileGuard, PypiIndex};
+
+/// The conda packages locked for a single environment, as stored in `pixi.lock`.
+pub type LockedCondaPackages = Vec<RepoDataRecord>;
+
+/// A single locked PyPI package, together with the extras it was selected for.
+pub type PypiRecord = (PypiPackageData, PypiPackageEnvironmentData);
+
+/// The PyPI packages locked for a single environment, as stored in `pixi.lock`.
+pub type LockedPypiPackages = Vec<PypiRecord>;
+
+use rattler_conda_types::{GenericVirtualPackage, MatchSpec};
+
+/// Re-solves the conda environment for a project and returns the newly locked packages, together
+/// with the [`LockFileGuard`] that was held for the duration of the solve.
+///
+/// `lock_file_path` is locked via [`lock_lock_file`] *before* solving starts, so that an editor, a
+/// shell hook, and CI all invoking `pixi install` on the same project at the same time serialize
+/// on the solve instead of racing to write `pixi.lock`. The caller must keep the returned guard
+/// alive until it has finished writing the newly solved packages to `pixi.lock`; dropping it early
+/// re-opens the race this function exists to close.
+///
+/// `constraints` and `exclude_newer` are forwarded straight to [`resolve_conda`]; see its docs for
+/// what they mean. `exclude_newer` is typically sourced from the manifest's
+/// `[project.exclude-newer]` setting (or a `--exclude-newer` CLI flag) so that reproducing a past
+/// solve is a matter of setting one value, not hand-filtering repodata.
+pub async fn update_conda_lock(
+    lock_file_path: &Path,
+    specs: Vec<MatchSpec>,
+    constraints: Vec<MatchSpec>,
+    virtual_packages: Vec<GenericVirtualPackage>,
+    locked_packages: Vec<RepoDataRecord>,
+    available_packages: Vec<Vec<RepoDataRecord>>,
+    exclude_newer: Option<DateTime<Utc>>,
+) -> miette::Result<(LockedCondaPackages, LockFileGuard)> {
+    let guard = lock_lock_file(lock_file_path).await?;
+
+    let locked = resolve_conda(
+        specs,
+        constraints,
+        virtual_packages,
+        locked_packages,
+        available_packages,
+        exclude_newer,
+    )
+    .await?;
+
+    Ok((locked, guard))
+}
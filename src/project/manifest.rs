@@ -0,0 +1,17 @@
+//! Types parsed out of a project's `pixi.toml` manifest that the lock file resolver needs.
+
+/// A single `[pypi-dependencies]` entry: a version/URL/path requirement on a PyPI package.
+#[derive(Debug, Clone)]
+pub struct PyPiRequirement {
+    pub version: Option<String>,
+    pub extras: Vec<String>,
+}
+
+/// The `[system-requirements]` section: the minimum platform guarantees the environment can rely
+/// on (e.g. libc version), used to filter which virtual packages are assumed present when solving.
+#[derive(Debug, Clone, Default)]
+pub struct SystemRequirements {
+    pub linux: Option<String>,
+    pub macos: Option<String>,
+    pub cuda: Option<String>,
+}